@@ -0,0 +1,206 @@
+/// Constraints for an integer control, e.g. brightness or exposure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Integer {
+    pub range: (i64, i64),
+    pub step: u64,
+    pub default: i64,
+}
+
+/// A single entry in a menu control.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MenuItem {
+    String(String),
+    Integer(i64),
+}
+
+/// Describes the shape and constraints of a control, as reported by the device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Representation {
+    Unknown,
+    Integer(Integer),
+    Boolean,
+    Menu(Vec<MenuItem>),
+    Button,
+    String,
+    Bitmask,
+}
+
+/// The current value of a control, as read from or written to a device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ControlValue {
+    Integer(i64),
+    Boolean(bool),
+    String(String),
+    Menu(u32),
+    Bitmask(u32),
+    Button,
+}
+
+impl Representation {
+    /// Wraps a raw integer read back from the device into the `ControlValue` variant that
+    /// matches this representation.
+    ///
+    /// v4l's own control value type has no separate variant for `Menu`/`Bitmask`/`Button`
+    /// controls (they all come back as a plain integer), so a caller reading a control has to
+    /// consult the representation to know how to interpret it. This keeps a `get_control`
+    /// followed by `set_control` of the same value passing `validate`.
+    pub fn wrap_integer(&self, raw: i64) -> ControlValue {
+        match self {
+            Representation::Boolean => ControlValue::Boolean(raw != 0),
+            Representation::Menu(_) => ControlValue::Menu(raw as u32),
+            Representation::Bitmask => ControlValue::Bitmask(raw as u32),
+            Representation::Button => ControlValue::Button,
+            Representation::String => ControlValue::String(raw.to_string()),
+            Representation::Integer(_) | Representation::Unknown => ControlValue::Integer(raw),
+        }
+    }
+
+    /// Checks whether `value` is a legal value to write for a control with this representation,
+    /// so callers get a descriptive error instead of an `EINVAL` from the device.
+    pub fn validate(&self, value: &ControlValue) -> Result<(), String> {
+        match (self, value) {
+            (Representation::Integer(constraints), ControlValue::Integer(v)) => {
+                let (min, max) = constraints.range;
+                if *v < min || *v > max {
+                    return Err(format!(
+                        "value {} out of range [{}, {}]",
+                        v, min, max
+                    ));
+                }
+                if constraints.step > 1 && (*v - min) as u64 % constraints.step != 0 {
+                    return Err(format!(
+                        "value {} is not a multiple of step {} from {}",
+                        v, constraints.step, min
+                    ));
+                }
+                Ok(())
+            }
+            (Representation::Boolean, ControlValue::Boolean(_)) => Ok(()),
+            (Representation::Menu(items), ControlValue::Menu(index)) => {
+                if *index as usize >= items.len() {
+                    return Err(format!(
+                        "menu index {} out of range (0..{})",
+                        index,
+                        items.len()
+                    ));
+                }
+                Ok(())
+            }
+            (Representation::Button, ControlValue::Button) => Ok(()),
+            (Representation::String, ControlValue::String(_)) => Ok(()),
+            (Representation::Bitmask, ControlValue::Bitmask(_)) => Ok(()),
+            // An `Unknown` representation has no constraints to check against, so any raw
+            // integer `wrap_integer` could have produced for it is accepted as-is.
+            (Representation::Unknown, ControlValue::Integer(_)) => Ok(()),
+            _ => Err("value does not match the control's representation".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_integer() {
+        let repr = Representation::Integer(Integer {
+            range: (0, 100),
+            step: 5,
+            default: 50,
+        });
+
+        let cases = [
+            (ControlValue::Integer(0), true),
+            (ControlValue::Integer(100), true),
+            (ControlValue::Integer(55), true),
+            (ControlValue::Integer(-1), false),
+            (ControlValue::Integer(101), false),
+            (ControlValue::Integer(53), false),
+            (ControlValue::Boolean(true), false),
+        ];
+        for (value, ok) in cases {
+            assert_eq!(repr.validate(&value).is_ok(), ok, "value: {:?}", value);
+        }
+    }
+
+    #[test]
+    fn validate_menu() {
+        let repr = Representation::Menu(vec![
+            MenuItem::String("a".to_string()),
+            MenuItem::String("b".to_string()),
+        ]);
+
+        let cases = [
+            (ControlValue::Menu(0), true),
+            (ControlValue::Menu(1), true),
+            (ControlValue::Menu(2), false),
+            (ControlValue::Integer(0), false),
+        ];
+        for (value, ok) in cases {
+            assert_eq!(repr.validate(&value).is_ok(), ok, "value: {:?}", value);
+        }
+    }
+
+    #[test]
+    fn validate_kind_mismatch() {
+        assert!(Representation::Boolean.validate(&ControlValue::Boolean(true)).is_ok());
+        assert!(Representation::Boolean.validate(&ControlValue::Integer(1)).is_err());
+        assert!(Representation::Button.validate(&ControlValue::Button).is_ok());
+        assert!(Representation::Button.validate(&ControlValue::Boolean(false)).is_err());
+        assert!(Representation::String.validate(&ControlValue::String("x".to_string())).is_ok());
+        assert!(Representation::Bitmask.validate(&ControlValue::Bitmask(0xF)).is_ok());
+        assert!(Representation::Unknown.validate(&ControlValue::Integer(0)).is_ok());
+    }
+
+    #[test]
+    fn get_then_set_round_trip() {
+        // `wrap_integer` followed by `validate` must never fail: this is exactly what a
+        // `get_control` result fed straight back into `set_control` does.
+        let reprs = [
+            Representation::Unknown,
+            Representation::Integer(Integer {
+                range: (0, 10),
+                step: 1,
+                default: 0,
+            }),
+            Representation::Boolean,
+            Representation::Menu(vec![MenuItem::String("a".to_string())]),
+            Representation::Button,
+            Representation::String,
+            Representation::Bitmask,
+        ];
+        for repr in reprs {
+            let value = repr.wrap_integer(0);
+            assert!(
+                repr.validate(&value).is_ok(),
+                "repr: {:?}, value: {:?}",
+                repr,
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn wrap_integer_matches_representation() {
+        let cases = [
+            (Representation::Boolean, 0, ControlValue::Boolean(false)),
+            (Representation::Boolean, 1, ControlValue::Boolean(true)),
+            (Representation::Menu(Vec::new()), 3, ControlValue::Menu(3)),
+            (Representation::Bitmask, 0xA, ControlValue::Bitmask(0xA)),
+            (Representation::Button, 1, ControlValue::Button),
+            (
+                Representation::Integer(Integer {
+                    range: (0, 10),
+                    step: 1,
+                    default: 0,
+                }),
+                7,
+                ControlValue::Integer(7),
+            ),
+            (Representation::Unknown, 42, ControlValue::Integer(42)),
+        ];
+        for (repr, raw, expected) in cases {
+            assert_eq!(repr.wrap_integer(raw), expected, "repr: {:?}", repr);
+        }
+    }
+}
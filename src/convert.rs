@@ -0,0 +1,220 @@
+use std::io;
+
+use ffimage::packed::DynamicImageView;
+
+use crate::format::FourCC;
+use crate::traits::Stream;
+
+/// Tells a caller of `Device::stream_as` whether frames arrive zero-copy or had to be
+/// transcoded in software to reach the requested format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionPath {
+    /// The device natively produces the requested format.
+    Native,
+    /// Frames are decoded from YUYV (4:2:2 packed) on every call to `next`.
+    Yuyv,
+    /// Frames are decoded from baseline JPEG (MJPG) on every call to `next`.
+    Mjpg,
+}
+
+/// Native formats this crate knows how to transcode into `CONVERTIBLE_TARGET`, in order of
+/// preference.
+pub(crate) const CONVERTIBLE: [(&[u8; 4], ConversionPath); 2] =
+    [(b"YUYV", ConversionPath::Yuyv), (b"MJPG", ConversionPath::Mjpg)];
+
+/// The only pixel format `yuyv_to_rgb`/`mjpg_to_rgb` know how to produce. `Device::stream_as`
+/// rejects any other `target` before entering the conversion path, rather than silently handing
+/// back packed RGB bytes mislabeled with whatever FourCC the caller asked for.
+pub(crate) const CONVERTIBLE_TARGET: FourCC = FourCC { repr: *b"RGB3" };
+
+fn bytes_per_pixel(_fourcc: FourCC) -> usize {
+    // Every target format this crate supports today is a packed 8-bit-per-channel RGB/BGR
+    // triple; revisit once a fourth channel or a different bit depth is added.
+    3
+}
+
+/// Expands a YUYV (4:2:2 packed) frame into packed RGB using BT.601 coefficients.
+///
+/// `stride` is the number of bytes between the start of one row and the next, as reported by the
+/// device; a stride of `0` means the device didn't report one, and rows are assumed to be
+/// tightly packed.
+fn yuyv_to_rgb(src: &[u8], width: usize, height: usize, stride: usize, dst: &mut [u8]) -> io::Result<()> {
+    let clamp = |v: i32| v.clamp(0, 255) as u8;
+
+    let row_bytes = width * 2;
+    let stride = if stride == 0 { row_bytes } else { stride };
+    if stride < row_bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "YUYV stride {} is too small for a {}-pixel-wide row ({} bytes)",
+                stride, width, row_bytes
+            ),
+        ));
+    }
+
+    let required = height * stride;
+    if src.len() < required {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "YUYV frame is only {} bytes, need {} for a {}x{} frame at stride {}",
+                src.len(),
+                required,
+                width,
+                height,
+                stride
+            ),
+        ));
+    }
+
+    for row in 0..height {
+        let src_row = &src[row * stride..row * stride + row_bytes];
+        let dst_row = &mut dst[row * width * 3..(row + 1) * width * 3];
+
+        for i in 0..width / 2 {
+            let o = i * 4;
+            let y0 = src_row[o] as i32;
+            let u = src_row[o + 1] as i32 - 128;
+            let y1 = src_row[o + 2] as i32;
+            let v = src_row[o + 3] as i32 - 128;
+
+            let d = i * 6;
+            dst_row[d] = clamp(y0 + 1402 * v / 1000);
+            dst_row[d + 1] = clamp(y0 - 344 * u / 1000 - 714 * v / 1000);
+            dst_row[d + 2] = clamp(y0 + 1772 * u / 1000);
+            dst_row[d + 3] = clamp(y1 + 1402 * v / 1000);
+            dst_row[d + 4] = clamp(y1 - 344 * u / 1000 - 714 * v / 1000);
+            dst_row[d + 5] = clamp(y1 + 1772 * u / 1000);
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes a baseline JPEG (MJPG) frame into packed RGB.
+fn mjpg_to_rgb(src: &[u8], width: usize, height: usize, dst: &mut [u8]) -> io::Result<()> {
+    let mut decoder = jpeg_decoder::Decoder::new(src);
+    let pixels = decoder
+        .decode()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+
+    // `decode` happily hands back grayscale (1 component) or CMYK (4 component) pixels for
+    // JPEGs that aren't plain RGB; blindly copying those into an RGB buffer would either
+    // silently corrupt colors or overrun `dst`, so the frame info has to be checked first.
+    let info = decoder
+        .info()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "JPEG decoder produced no frame info"))?;
+    if info.pixel_format != jpeg_decoder::PixelFormat::RGB24 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported JPEG pixel format {:?}, only RGB24 is supported", info.pixel_format),
+        ));
+    }
+    if info.width as usize != width || info.height as usize != height {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "JPEG frame is {}x{}, expected {}x{}",
+                info.width, info.height, width, height
+            ),
+        ));
+    }
+    if pixels.len() != dst.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("decoded JPEG has {} bytes, expected {}", pixels.len(), dst.len()),
+        ));
+    }
+
+    dst.copy_from_slice(&pixels);
+    Ok(())
+}
+
+/// Wraps a native [`Stream`] and transcodes each frame into `target` on the fly, reusing a
+/// single output buffer across calls to avoid per-frame allocation.
+pub(crate) struct ConvertingStream<'a> {
+    inner: Box<dyn Stream<Item = DynamicImageView> + 'a>,
+    path: ConversionPath,
+    width: usize,
+    height: usize,
+    stride: usize,
+    target: FourCC,
+    buf: Vec<u8>,
+    view: Option<DynamicImageView>,
+}
+
+impl<'a> ConvertingStream<'a> {
+    pub fn new(
+        inner: Box<dyn Stream<Item = DynamicImageView> + 'a>,
+        path: ConversionPath,
+        width: usize,
+        height: usize,
+        stride: usize,
+        target: FourCC,
+    ) -> Self {
+        let buf = vec![0u8; width * height * bytes_per_pixel(target)];
+        ConvertingStream {
+            inner,
+            path,
+            width,
+            height,
+            stride,
+            target,
+            buf,
+            view: None,
+        }
+    }
+
+    pub fn path(&self) -> ConversionPath {
+        self.path
+    }
+}
+
+impl<'a> Stream for ConvertingStream<'a> {
+    type Item = DynamicImageView;
+
+    fn next(&mut self) -> io::Result<&Self::Item> {
+        let frame = self.inner.next()?;
+        let src: &[u8] = frame.raw();
+
+        match self.path {
+            ConversionPath::Yuyv => yuyv_to_rgb(src, self.width, self.height, self.stride, &mut self.buf)?,
+            ConversionPath::Mjpg => mjpg_to_rgb(src, self.width, self.height, &mut self.buf)?,
+            ConversionPath::Native => self.buf.copy_from_slice(src),
+        }
+
+        self.view = Some(DynamicImageView::new(&self.buf, self.width, self.height));
+        Ok(self.view.as_ref().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yuyv_to_rgb_known_swatch() {
+        // One YUYV macropixel (y0, u, y1, v) = (100, 200, 150, 50), hand-computed through the
+        // BT.601 coefficients used above.
+        let src = [100u8, 200, 150, 50];
+        let mut dst = [0u8; 6];
+        yuyv_to_rgb(&src, 2, 1, 0, &mut dst).unwrap();
+        assert_eq!(dst, [0, 131, 227, 41, 181, 255]);
+    }
+
+    #[test]
+    fn yuyv_to_rgb_rejects_stride_smaller_than_row() {
+        let src = [0u8; 4];
+        let mut dst = [0u8; 6];
+        assert!(yuyv_to_rgb(&src, 2, 1, 2, &mut dst).is_err());
+    }
+
+    #[test]
+    fn yuyv_to_rgb_rejects_truncated_frame() {
+        // One byte short of the single packed row a 2x1 frame needs.
+        let src = [0u8; 3];
+        let mut dst = [0u8; 6];
+        assert!(yuyv_to_rgb(&src, 2, 1, 0, &mut dst).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::control::Representation;
+use crate::format::FourCC;
+
+/// Static information about a single control exposed by a device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ControlInfo {
+    pub id: u32,
+    pub name: String,
+    pub repr: Representation,
+}
+
+/// A single frame interval fraction, `(numerator, denominator)`, as reported by the device. The
+/// frame rate in frames per second is `denominator / numerator`.
+pub type Interval = (u32, u32);
+
+/// A resolution a device supports for a given pixel format, as reported during enumeration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Resolution {
+    /// An exact width/height the device can be set to, along with the frame intervals it
+    /// supports at that size.
+    Discrete {
+        width: u32,
+        height: u32,
+        intervals: Vec<Interval>,
+    },
+    /// A range of widths/heights the device accepts, each in multiples of `step_*` starting at
+    /// `min_*`. A continuous range is a stepwise one with `step_width`/`step_height` of 1.
+    Stepwise {
+        min_width: u32,
+        max_width: u32,
+        step_width: u32,
+        min_height: u32,
+        max_height: u32,
+        step_height: u32,
+    },
+}
+
+impl Resolution {
+    /// Fits `(width, height)` to the values this descriptor actually supports: `Discrete` only
+    /// accepts an exact match, `Stepwise` rounds down to the nearest step within range.
+    pub fn clamp(&self, width: u32, height: u32) -> Option<(u32, u32)> {
+        match self {
+            Resolution::Discrete {
+                width: w,
+                height: h,
+                ..
+            } => (width == *w && height == *h).then_some((width, height)),
+            Resolution::Stepwise {
+                min_width,
+                max_width,
+                step_width,
+                min_height,
+                max_height,
+                step_height,
+            } => {
+                let round = |value: u32, min: u32, max: u32, step: u32| -> u32 {
+                    let value = value.clamp(min, max);
+                    if step == 0 {
+                        value
+                    } else {
+                        min + (value - min) / step * step
+                    }
+                };
+                Some((
+                    round(width, *min_width, *max_width, *step_width),
+                    round(height, *min_height, *max_height, *step_height),
+                ))
+            }
+        }
+    }
+}
+
+/// Static information about a single pixel format supported by a device.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormatInfo {
+    pub fourcc: FourCC,
+    pub resolutions: Vec<Resolution>,
+    pub emulated: bool,
+}
+
+/// Static information about a device, as discovered during enumeration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Info {
+    pub index: u32,
+    pub name: String,
+    pub formats: Vec<FormatInfo>,
+    pub controls: Vec<ControlInfo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_discrete() {
+        let res = Resolution::Discrete {
+            width: 640,
+            height: 480,
+            intervals: Vec::new(),
+        };
+
+        let cases = [
+            (640, 480, Some((640, 480))),
+            (641, 480, None),
+            (640, 479, None),
+            (0, 0, None),
+        ];
+        for (width, height, expected) in cases {
+            assert_eq!(res.clamp(width, height), expected, "{}x{}", width, height);
+        }
+    }
+
+    #[test]
+    fn clamp_stepwise() {
+        let res = Resolution::Stepwise {
+            min_width: 160,
+            max_width: 640,
+            step_width: 16,
+            min_height: 120,
+            max_height: 480,
+            step_height: 12,
+        };
+
+        let cases = [
+            // Exact step boundaries pass through unchanged.
+            (160, 120, (160, 120)),
+            (640, 480, (640, 480)),
+            // Values below the minimum clamp up to it.
+            (0, 0, (160, 120)),
+            // Values above the maximum clamp down to it.
+            (1000, 1000, (640, 480)),
+            // Off-step values round down to the nearest step.
+            (170, 130, (160, 120)),
+            (191, 143, (176, 132)),
+        ];
+        for (width, height, expected) in cases {
+            assert_eq!(res.clamp(width, height), Some(expected), "{}x{}", width, height);
+        }
+    }
+
+    #[test]
+    fn clamp_stepwise_zero_step_passes_through() {
+        let res = Resolution::Stepwise {
+            min_width: 160,
+            max_width: 640,
+            step_width: 0,
+            min_height: 120,
+            max_height: 480,
+            step_height: 0,
+        };
+
+        assert_eq!(res.clamp(333, 222), Some((333, 222)));
+        assert_eq!(res.clamp(0, 0), Some((160, 120)));
+        assert_eq!(res.clamp(9999, 9999), Some((640, 480)));
+    }
+}
@@ -0,0 +1,40 @@
+/// A four character code identifying a pixel format, e.g. `YUYV` or `MJPG`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FourCC {
+    pub repr: [u8; 4],
+}
+
+impl FourCC {
+    pub fn new(repr: &[u8; 4]) -> Self {
+        FourCC { repr: *repr }
+    }
+}
+
+/// Describes the pixel layout of a captured frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Format {
+    pub width: u32,
+    pub height: u32,
+    pub fourcc: FourCC,
+    pub stride: usize,
+}
+
+impl Format {
+    pub fn new(width: u32, height: u32, fourcc: FourCC) -> Self {
+        Format {
+            width,
+            height,
+            fourcc,
+            stride: 0,
+        }
+    }
+
+    pub fn with_stride(width: u32, height: u32, fourcc: FourCC, stride: usize) -> Self {
+        Format {
+            width,
+            height,
+            fourcc,
+            stride,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+pub(crate) mod traits;
+
+#[cfg(all(target_os = "linux", not(feature = "uvc")))]
+mod v4l2;
+#[cfg(all(target_os = "linux", not(feature = "uvc")))]
+pub(crate) use v4l2::{PlatformDevice, PlatformList};
+
+// libuvc gives us macOS/Windows support, at the cost of a much thinner feature set than v4l2
+// (no control enumeration, no stepwise frame sizes). It can also be forced on Linux with the
+// `uvc` feature, mostly for testing the backend without a second machine.
+#[cfg(any(feature = "uvc", not(target_os = "linux")))]
+mod uvc;
+#[cfg(any(feature = "uvc", not(target_os = "linux")))]
+pub(crate) use uvc::{PlatformDevice, PlatformList};
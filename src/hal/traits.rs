@@ -0,0 +1,158 @@
+use std::io;
+
+use ffimage::packed::DynamicImageView;
+
+use crate::control::ControlValue;
+use crate::convert::{ConversionPath, ConvertingStream, CONVERTIBLE, CONVERTIBLE_TARGET};
+use crate::device::FormatInfo;
+use crate::format::{Format, FourCC};
+use crate::traits::Stream;
+
+/// Selects how a stream exchanges buffers with the capture driver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IoMethod {
+    /// Kernel-allocated buffers mapped into this process. Zero-copy and the default; what most
+    /// devices should use.
+    Mmap,
+    /// Buffers allocated by this process and handed to the kernel. Zero-copy, at the cost of
+    /// page-aligned allocations.
+    UserPtr,
+    /// The plain `read()`/`write()` syscall interface. The only I/O method some very old
+    /// devices support.
+    Read,
+}
+
+/// Configures the queue depth and buffer exchange strategy used by a stream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamConfig {
+    pub io_method: IoMethod,
+    pub buffer_count: u32,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        StreamConfig {
+            io_method: IoMethod::Mmap,
+            buffer_count: 4,
+        }
+    }
+}
+
+/// Platform-specific device backend.
+///
+/// Implementors talk to the underlying OS capture API (e.g. v4l2) and are wrapped by the
+/// public, platform-agnostic device types.
+pub trait Device {
+    /// Returns the format currently configured on the device.
+    fn get_format(&mut self) -> io::Result<Format>;
+
+    /// Requests a new format and returns the format the device actually settled on.
+    fn set_format(&mut self, fmt: &Format) -> io::Result<Format>;
+
+    /// Returns the frame interval (in frames per second) currently configured on the device.
+    fn get_interval(&mut self) -> io::Result<u32>;
+
+    /// Requests a new frame interval and returns the one the device actually settled on.
+    fn set_interval(&mut self, fps: u32) -> io::Result<u32>;
+
+    /// Returns the pixel formats, resolutions and frame intervals this device advertises.
+    fn formats(&self) -> &[FormatInfo];
+
+    /// Reads the current value of the control identified by `id`.
+    fn get_control(&mut self, id: u32) -> io::Result<ControlValue>;
+
+    /// Writes `value` to the control identified by `id`.
+    fn set_control(&mut self, id: u32, value: ControlValue) -> io::Result<()>;
+
+    /// Starts streaming with the given buffer I/O strategy and queue depth, and returns a
+    /// handle to pull frames from.
+    fn stream_with<'a>(
+        &'a mut self,
+        cfg: StreamConfig,
+    ) -> io::Result<Box<dyn Stream<Item = DynamicImageView> + 'a>>;
+
+    /// Starts streaming with the default strategy (mmap, 4 buffers) and returns a handle to
+    /// pull frames from.
+    fn stream<'a>(&'a mut self) -> io::Result<Box<dyn Stream<Item = DynamicImageView> + 'a>>
+    where
+        Self: Sized,
+    {
+        self.stream_with(StreamConfig::default())
+    }
+
+    /// Starts streaming, transparently converting frames to `target` in software if the device
+    /// cannot deliver it natively.
+    ///
+    /// Software conversion only produces packed `RGB3` (from `YUYV` or `MJPG`); any other
+    /// `target` the device cannot deliver natively is rejected with `InvalidInput`.
+    ///
+    /// Returns the path the resulting stream takes alongside the stream itself, so callers can
+    /// tell whether frames are zero-copy or had to be transcoded.
+    fn stream_as<'a>(
+        &'a mut self,
+        target: FourCC,
+    ) -> io::Result<(ConversionPath, Box<dyn Stream<Item = DynamicImageView> + 'a>)>
+    where
+        Self: Sized,
+    {
+        let native = self.get_format()?;
+        if native.fourcc == target {
+            return Ok((ConversionPath::Native, self.stream()?));
+        }
+
+        if self.formats().iter().any(|f| f.fourcc == target) {
+            let probe = Format::new(native.width, native.height, target);
+            if let Ok(settled) = self.set_format(&probe) {
+                if settled.fourcc == target {
+                    return Ok((ConversionPath::Native, self.stream()?));
+                }
+            }
+        }
+
+        // Every conversion path in `convert.rs` produces packed RGB3 and nothing else; without
+        // this check a junk or merely unsupported `target` (e.g. `NV12`) would silently succeed,
+        // handing back RGB3 bytes mislabeled with whatever FourCC the caller asked for.
+        if target != CONVERTIBLE_TARGET {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot convert to {:?}: software conversion only produces {:?}",
+                    target, CONVERTIBLE_TARGET
+                ),
+            ));
+        }
+
+        // Only try source formats the device actually advertises, and prefer a non-emulated one:
+        // an emulated format is already software-synthesized by the driver from some other
+        // native format, so converting from it here would mean transcoding twice.
+        let mut candidates: Vec<(FourCC, ConversionPath, bool)> = CONVERTIBLE
+            .iter()
+            .filter_map(|&(raw_fourcc, path)| {
+                let fourcc = FourCC::new(raw_fourcc);
+                self.formats()
+                    .iter()
+                    .find(|f| f.fourcc == fourcc)
+                    .map(|f| (fourcc, path, f.emulated))
+            })
+            .collect();
+        candidates.sort_by_key(|&(_, _, emulated)| emulated);
+
+        for (fourcc, path, _) in candidates {
+            let probe = Format::new(native.width, native.height, fourcc);
+            let settled = match self.set_format(&probe) {
+                Ok(settled) if settled.fourcc == fourcc => settled,
+                _ => continue,
+            };
+
+            let width = settled.width as usize;
+            let height = settled.height as usize;
+            let stream = ConvertingStream::new(self.stream()?, path, width, height, settled.stride, target);
+            return Ok((path, Box::new(stream)));
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "no native or convertible format found for the requested FourCC",
+        ))
+    }
+}
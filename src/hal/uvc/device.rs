@@ -0,0 +1,208 @@
+use std::io;
+
+use ffimage::packed::DynamicImageView;
+
+use uvc::{Context, Device as UvcDevice, DeviceHandle, FrameFormat};
+
+use crate::control::ControlValue;
+use crate::device::{FormatInfo, Info as DeviceInfo, Resolution};
+use crate::format::{Format, FourCC};
+use crate::hal::traits::{Device, StreamConfig};
+use crate::hal::uvc::stream::PlatformStream;
+use crate::traits::Stream;
+
+fn map_err<E: std::fmt::Display>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err.to_string())
+}
+
+fn format_to_fourcc(format: FrameFormat) -> FourCC {
+    match format {
+        FrameFormat::YUYV => FourCC::new(b"YUYV"),
+        FrameFormat::MJPEG => FourCC::new(b"MJPG"),
+        _ => FourCC::new(b"UNKN"),
+    }
+}
+
+pub(super) fn fourcc_to_format(fourcc: FourCC) -> FrameFormat {
+    match &fourcc.repr {
+        b"YUYV" => FrameFormat::YUYV,
+        b"MJPG" => FrameFormat::MJPEG,
+        _ => FrameFormat::Any,
+    }
+}
+
+// libuvc's `Context` has to outlive every `Device`/`DeviceHandle` borrowed from it, and we have
+// no natural owner to hang that lifetime off since `PlatformList`/`PlatformDevice` are created
+// and destroyed independently of each other. Cameras are effectively process-lifetime
+// singletons, so we leak a single context once and hand out `'static` handles from it rather
+// than threading a lifetime through every public type in this crate.
+fn context() -> io::Result<&'static Context<'static>> {
+    use std::sync::OnceLock;
+    static CONTEXT: OnceLock<Context<'static>> = OnceLock::new();
+
+    if let Some(ctx) = CONTEXT.get() {
+        return Ok(ctx);
+    }
+    let ctx = Context::new().map_err(map_err)?;
+    Ok(CONTEXT.get_or_init(|| ctx))
+}
+
+pub(crate) struct PlatformList {}
+
+impl PlatformList {
+    pub fn enumerate() -> Vec<DeviceInfo> {
+        let mut list = Vec::new();
+
+        let ctx = match context() {
+            Ok(ctx) => ctx,
+            Err(_) => return list,
+        };
+        let devices = match ctx.devices() {
+            Ok(devices) => devices,
+            Err(_) => return list,
+        };
+
+        for (index, dev) in devices.enumerate() {
+            let formats = match PlatformDevice::open(&dev).map(|dev| dev.formats.clone()) {
+                Ok(formats) => formats,
+                Err(_) => continue,
+            };
+            let name = dev
+                .description()
+                .ok()
+                .and_then(|desc| desc.product)
+                .unwrap_or_else(|| format!("UVC camera {}", index));
+
+            list.push(DeviceInfo {
+                index: index as u32,
+                name,
+                formats,
+                // libuvc exposes UVC "terms"/"units" rather than the flat control list v4l2
+                // gives us; until that's mapped, controls are simply not enumerated here.
+                controls: Vec::new(),
+            });
+        }
+
+        list
+    }
+}
+
+/// Enumerates the pixel formats, resolutions and frame intervals a libuvc device handle
+/// supports.
+fn list_formats(handle: &DeviceHandle<'static>) -> Vec<FormatInfo> {
+    let mut formats = Vec::new();
+
+    for plat_format in handle.supported_formats() {
+        let mut info = FormatInfo {
+            fourcc: format_to_fourcc(plat_format.subtype()),
+            resolutions: Vec::new(),
+            emulated: false,
+        };
+
+        for plat_size in plat_format.supported_formats() {
+            let intervals = plat_size.intervals().iter().map(|fps| (1, *fps)).collect();
+            info.resolutions.push(Resolution::Discrete {
+                width: plat_size.width() as u32,
+                height: plat_size.height() as u32,
+                intervals,
+            });
+        }
+
+        formats.push(info);
+    }
+
+    formats
+}
+
+pub(crate) struct PlatformDevice {
+    handle: DeviceHandle<'static>,
+    format: Format,
+    formats: Vec<FormatInfo>,
+}
+
+impl PlatformDevice {
+    fn open(dev: &UvcDevice<'static>) -> io::Result<Self> {
+        let handle = dev.open().map_err(map_err)?;
+        let formats = list_formats(&handle);
+        Ok(PlatformDevice {
+            handle,
+            format: Format::new(640, 480, FourCC::new(b"YUYV")),
+            formats,
+        })
+    }
+
+    pub fn new(index: usize) -> io::Result<Self> {
+        let ctx = context()?;
+        let devices = ctx.devices().map_err(map_err)?;
+        let dev = devices
+            .into_iter()
+            .nth(index)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no such UVC device"))?;
+        Self::open(&dev)
+    }
+
+    pub fn inner_mut(&mut self) -> &mut DeviceHandle<'static> {
+        &mut self.handle
+    }
+}
+
+impl Device for PlatformDevice {
+    fn get_format(&mut self) -> io::Result<Format> {
+        Ok(self.format)
+    }
+
+    fn formats(&self) -> &[FormatInfo] {
+        &self.formats
+    }
+
+    fn set_format(&mut self, fmt: &Format) -> io::Result<Format> {
+        // libuvc only negotiates a format when a stream is actually started, so validate the
+        // request against what the device advertises and remember it for `stream()`.
+        let format = fourcc_to_format(fmt.fourcc);
+        self.handle
+            .get_stream_handle_with_format_size(format, fmt.width as i32, fmt.height as i32)
+            .map_err(map_err)?;
+        self.format = *fmt;
+        Ok(self.format)
+    }
+
+    fn get_interval(&mut self) -> io::Result<u32> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "frame interval control is not implemented for the libuvc backend yet",
+        ))
+    }
+
+    fn set_interval(&mut self, _fps: u32) -> io::Result<u32> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "frame interval control is not implemented for the libuvc backend yet",
+        ))
+    }
+
+    fn get_control(&mut self, _id: u32) -> io::Result<ControlValue> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "control access is not implemented for the libuvc backend yet",
+        ))
+    }
+
+    fn set_control(&mut self, _id: u32, _value: ControlValue) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "control access is not implemented for the libuvc backend yet",
+        ))
+    }
+
+    fn stream_with<'a>(
+        &'a mut self,
+        cfg: StreamConfig,
+    ) -> io::Result<Box<dyn Stream<Item = DynamicImageView> + 'a>> {
+        // libuvc only manages its own internal transfer queue; it has no userptr/read mode and
+        // no API to size that queue, so every `IoMethod`/`buffer_count` combination behaves the
+        // same here.
+        let _ = cfg;
+        let stream = PlatformStream::new(self)?;
+        Ok(Box::new(stream))
+    }
+}
@@ -0,0 +1,5 @@
+mod device;
+mod stream;
+
+pub(crate) use device::{PlatformDevice, PlatformList};
+pub(crate) use stream::PlatformStream;
@@ -0,0 +1,74 @@
+use std::io;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+
+use ffimage::packed::DynamicImageView;
+
+use uvc::{ActiveStream, StreamHandle};
+
+use crate::format::Format;
+use crate::hal::traits::Device;
+use crate::hal::uvc::device::{fourcc_to_format, PlatformDevice};
+use crate::traits::Stream;
+
+/// How many frames the libuvc callback is allowed to get ahead of the consumer before it starts
+/// dropping them.
+const CHANNEL_DEPTH: usize = 4;
+
+pub(crate) struct PlatformStream<'a> {
+    // Kept alive only to keep the stream running; frames arrive through `rx`.
+    _active: ActiveStream<'a, SyncSender<Vec<u8>>>,
+    rx: Receiver<Vec<u8>>,
+    format: Format,
+    view: Option<DynamicImageView>,
+}
+
+impl<'a> PlatformStream<'a> {
+    pub fn new(dev: &'a mut PlatformDevice) -> io::Result<Self> {
+        let format = dev.get_format()?;
+        let (tx, rx) = sync_channel(CHANNEL_DEPTH);
+
+        let stream_handle: StreamHandle<'a> = dev
+            .inner_mut()
+            .get_stream_handle_with_format_size(
+                fourcc_to_format(format.fourcc),
+                format.width as i32,
+                format.height as i32,
+            )
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+
+        let active = stream_handle
+            .start_stream(
+                |frame, tx: &mut SyncSender<Vec<u8>>| {
+                    // Best-effort delivery: a full channel means the consumer fell behind, so we
+                    // drop the frame rather than block the capture callback.
+                    let _ = tx.try_send(frame.to_bytes().to_vec());
+                },
+                tx,
+            )
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+
+        Ok(PlatformStream {
+            _active: active,
+            rx,
+            format,
+            view: None,
+        })
+    }
+}
+
+impl<'a> Stream for PlatformStream<'a> {
+    type Item = DynamicImageView;
+
+    fn next(&mut self) -> io::Result<&Self::Item> {
+        let buf = self
+            .rx
+            .recv()
+            .map_err(|err| io::Error::new(io::ErrorKind::BrokenPipe, err.to_string()))?;
+        self.view = Some(DynamicImageView::new(
+            &buf,
+            self.format.width as usize,
+            self.format.height as usize,
+        ));
+        Ok(self.view.as_ref().unwrap())
+    }
+}
@@ -1,19 +1,158 @@
 use std::{io, path::Path};
 
-use v4l::capture::{Device as CaptureDevice, Format as CaptureFormat};
-use v4l::control::{MenuItem as ControlMenuItem, Type as ControlType};
+use v4l::capture::{
+    Device as CaptureDevice, Format as CaptureFormat, Parameters as CaptureParameters,
+};
+use v4l::control::{
+    Control as PlatformControl, MenuItem as ControlMenuItem, Type as ControlType,
+    Value as PlatformControlValue,
+};
 use v4l::DeviceList;
 use v4l::FourCC as FourCC_;
 
 use ffimage::packed::DynamicImageView;
 
 use crate::control;
-use crate::device::{ControlInfo, FormatInfo, Info as DeviceInfo};
+use crate::control::ControlValue;
+use crate::device::{ControlInfo, FormatInfo, Info as DeviceInfo, Resolution};
 use crate::format::{Format, FourCC};
-use crate::hal::traits::Device;
+use crate::hal::traits::{Device, StreamConfig};
 use crate::hal::v4l2::stream::PlatformStream;
 use crate::traits::Stream;
 
+/// Translates the v4l control descriptions into our platform-agnostic `ControlInfo` list.
+fn parse_controls(plat_controls: impl IntoIterator<Item = v4l::control::Description>) -> Vec<ControlInfo> {
+    let mut controls = Vec::new();
+
+    for control in plat_controls {
+        let mut repr = control::Representation::Unknown;
+        match control.typ {
+            ControlType::Integer | ControlType::Integer64 => {
+                let constraints = control::Integer {
+                    range: (control.minimum as i64, control.maximum as i64),
+                    step: control.step as u64,
+                    default: control.default as i64,
+                };
+                repr = control::Representation::Integer(constraints);
+            }
+            ControlType::Boolean => {
+                repr = control::Representation::Boolean;
+            }
+            ControlType::Menu => {
+                let mut items = Vec::new();
+                if let Some(plat_items) = control.items {
+                    for plat_item in plat_items {
+                        match plat_item.1 {
+                            ControlMenuItem::Name(name) => {
+                                items.push(control::MenuItem::String(name));
+                            }
+                            ControlMenuItem::Value(value) => {
+                                items.push(control::MenuItem::Integer(value));
+                            }
+                        }
+                    }
+                }
+                repr = control::Representation::Menu(items);
+            }
+            ControlType::Button => {
+                repr = control::Representation::Button;
+            }
+            ControlType::String => {
+                repr = control::Representation::String;
+            }
+            ControlType::Bitmask => {
+                repr = control::Representation::Bitmask;
+            }
+            _ => {}
+        }
+
+        controls.push(ControlInfo {
+            id: control.id,
+            name: control.name,
+            repr,
+        })
+    }
+
+    controls
+}
+
+/// Enumerates the pixel formats, resolutions and frame intervals a v4l capture device supports.
+fn list_formats(dev: &CaptureDevice) -> io::Result<Vec<FormatInfo>> {
+    let mut formats = Vec::new();
+
+    for format in dev.enumerate_formats()? {
+        let mut info = FormatInfo {
+            fourcc: FourCC::new(&format.fourcc.repr),
+            resolutions: Vec::new(),
+            emulated: format.flags & v4l::format::Flags::EMULATED == v4l::format::Flags::EMULATED,
+        };
+
+        let plat_sizes = match dev.enumerate_framesizes(format.fourcc) {
+            Ok(plat_sizes) => plat_sizes,
+            Err(_) => {
+                formats.push(info);
+                continue;
+            }
+        };
+
+        for plat_size in plat_sizes {
+            match plat_size.size {
+                v4l::framesize::FrameSizeEnum::Discrete(size) => {
+                    let intervals = list_intervals(dev, format.fourcc, size.width, size.height);
+                    info.resolutions.push(Resolution::Discrete {
+                        width: size.width,
+                        height: size.height,
+                        intervals,
+                    });
+                }
+                v4l::framesize::FrameSizeEnum::Stepwise(step) => {
+                    info.resolutions.push(Resolution::Stepwise {
+                        min_width: step.min_width,
+                        max_width: step.max_width,
+                        step_width: step.step_width,
+                        min_height: step.min_height,
+                        max_height: step.max_height,
+                        step_height: step.step_height,
+                    });
+                }
+            }
+        }
+
+        formats.push(info);
+    }
+
+    Ok(formats)
+}
+
+/// Enumerates the frame intervals a v4l capture device supports at a given resolution.
+fn list_intervals(
+    dev: &CaptureDevice,
+    fourcc: v4l::FourCC,
+    width: u32,
+    height: u32,
+) -> Vec<(u32, u32)> {
+    let mut intervals = Vec::new();
+
+    let plat_intervals = match dev.enum_frameintervals(fourcc, width, height) {
+        Ok(plat_intervals) => plat_intervals,
+        Err(_) => return intervals,
+    };
+
+    for plat_interval in plat_intervals {
+        match plat_interval.interval {
+            v4l::frameinterval::FrameIntervalEnum::Discrete(frac) => {
+                intervals.push((frac.numerator, frac.denominator));
+            }
+            v4l::frameinterval::FrameIntervalEnum::Stepwise(step) => {
+                intervals.push((step.min.numerator, step.min.denominator));
+                intervals.push((step.max.numerator, step.max.denominator));
+            }
+        }
+    }
+
+    intervals
+}
+
 pub(crate) struct PlatformList {}
 
 impl PlatformList {
@@ -45,92 +184,22 @@ impl PlatformList {
                 continue;
             }
 
-            let mut controls = Vec::new();
             let plat_controls = dev.query_controls();
             if plat_controls.is_err() {
                 continue;
             }
+            let controls = parse_controls(plat_controls.unwrap());
 
-            for control in plat_controls.unwrap() {
-                let mut repr = control::Representation::Unknown;
-                match control.typ {
-                    ControlType::Integer | ControlType::Integer64 => {
-                        let constraints = control::Integer {
-                            range: (control.minimum as i64, control.maximum as i64),
-                            step: control.step as u64,
-                            default: control.default as i64,
-                        };
-                        repr = control::Representation::Integer(constraints);
-                    }
-                    ControlType::Boolean => {
-                        repr = control::Representation::Boolean;
-                    }
-                    ControlType::Menu => {
-                        let mut items = Vec::new();
-                        if let Some(plat_items) = control.items {
-                            for plat_item in plat_items {
-                                match plat_item.1 {
-                                    ControlMenuItem::Name(name) => {
-                                        items.push(control::MenuItem::String(name));
-                                    }
-                                    ControlMenuItem::Value(value) => {
-                                        items.push(control::MenuItem::Integer(value));
-                                    }
-                                }
-                            }
-                        }
-                        repr = control::Representation::Menu(items);
-                    }
-                    ControlType::Button => {
-                        repr = control::Representation::Button;
-                    }
-                    ControlType::String => {
-                        repr = control::Representation::String;
-                    }
-                    ControlType::Bitmask => {
-                        repr = control::Representation::Bitmask;
-                    }
-                    _ => {}
-                }
-
-                controls.push(ControlInfo {
-                    id: control.id,
-                    name: control.name,
-                    repr,
-                })
-            }
-
-            let mut formats = Vec::new();
             let dev = PlatformDevice::new(index);
             if dev.is_err() {
                 continue;
             }
 
             let dev = dev.unwrap();
-            let plat_formats = dev.inner.enumerate_formats();
-            if plat_formats.is_err() {
-                continue;
-            }
-
-            for format in plat_formats.unwrap() {
-                let plat_sizes = dev.inner.enumerate_framesizes(format.fourcc);
-                if plat_sizes.is_err() {
-                    continue;
-                }
-                let mut info = FormatInfo {
-                    fourcc: FourCC::new(&format.fourcc.repr),
-                    resolutions: Vec::new(),
-                    emulated: format.flags & v4l::format::Flags::EMULATED
-                        == v4l::format::Flags::EMULATED,
-                };
-                for plat_size in plat_sizes.unwrap() {
-                    // TODO: consider stepwise formats
-                    if let v4l::framesize::FrameSizeEnum::Discrete(size) = plat_size.size {
-                        info.resolutions.push((size.width, size.height));
-                    }
-                }
-                formats.push(info);
-            }
+            let formats = match list_formats(dev.inner()) {
+                Ok(formats) => formats,
+                Err(_) => continue,
+            };
 
             list.push(DeviceInfo {
                 index: index as u32,
@@ -146,21 +215,64 @@ impl PlatformList {
 
 pub(crate) struct PlatformDevice {
     inner: CaptureDevice,
+    controls: Vec<ControlInfo>,
+    formats: Vec<FormatInfo>,
 }
 
 impl PlatformDevice {
     pub fn new(index: usize) -> io::Result<Self> {
-        let dev = PlatformDevice {
-            inner: CaptureDevice::new(index)?,
-        };
-        Ok(dev)
+        let inner = CaptureDevice::new(index)?;
+        let controls = parse_controls(inner.query_controls()?);
+        let formats = list_formats(&inner)?;
+        Ok(PlatformDevice {
+            inner,
+            controls,
+            formats,
+        })
     }
 
     pub fn with_path<P: AsRef<Path>>(path: P) -> io::Result<Self> {
-        let dev = PlatformDevice {
-            inner: CaptureDevice::with_path(path)?,
+        let inner = CaptureDevice::with_path(path)?;
+        let controls = parse_controls(inner.query_controls()?);
+        let formats = list_formats(&inner)?;
+        Ok(PlatformDevice {
+            inner,
+            controls,
+            formats,
+        })
+    }
+
+    fn control_repr(&self, id: u32) -> io::Result<&control::Representation> {
+        self.controls
+            .iter()
+            .find(|info| info.id == id)
+            .map(|info| &info.repr)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("unknown control {}", id)))
+    }
+
+    /// Fits `width`/`height` to a resolution the device actually advertises for `fourcc`,
+    /// clamping against the first stepwise descriptor if no descriptor matches exactly. The
+    /// request is passed through unchanged if `fourcc` is not advertised or none of its
+    /// descriptors can fit it.
+    fn clamp_resolution(&self, fourcc: FourCC, width: u32, height: u32) -> (u32, u32) {
+        let format = match self.formats.iter().find(|f| f.fourcc == fourcc) {
+            Some(format) => format,
+            None => return (width, height),
         };
-        Ok(dev)
+
+        if format
+            .resolutions
+            .iter()
+            .any(|r| matches!(r.clamp(width, height), Some((w, h)) if (w, h) == (width, height)))
+        {
+            return (width, height);
+        }
+
+        format
+            .resolutions
+            .iter()
+            .find_map(|r| r.clamp(width, height))
+            .unwrap_or((width, height))
     }
 
     pub fn inner(&self) -> &CaptureDevice {
@@ -183,14 +295,68 @@ impl Device for PlatformDevice {
         ))
     }
 
+    fn formats(&self) -> &[FormatInfo] {
+        &self.formats
+    }
+
     fn set_format(&mut self, fmt: &Format) -> io::Result<Format> {
-        let fmt = CaptureFormat::new(fmt.width, fmt.height, FourCC_::new(&fmt.fourcc.repr));
+        let (width, height) = self.clamp_resolution(fmt.fourcc, fmt.width, fmt.height);
+        let fmt = CaptureFormat::new(width, height, FourCC_::new(&fmt.fourcc.repr));
         self.inner.set_format(&fmt)?;
         self.get_format()
     }
 
-    fn stream<'a>(&'a mut self) -> io::Result<Box<dyn Stream<Item = DynamicImageView> + 'a>> {
-        let stream = PlatformStream::new(self)?;
+    fn get_interval(&mut self) -> io::Result<u32> {
+        let params = self.inner.params()?;
+        let frac = params.interval;
+        if frac.numerator == 0 {
+            return Ok(0);
+        }
+        Ok(frac.denominator / frac.numerator)
+    }
+
+    fn set_interval(&mut self, fps: u32) -> io::Result<u32> {
+        let params = CaptureParameters::with_fps(fps);
+        self.inner.set_params(&params)?;
+        self.get_interval()
+    }
+
+    fn get_control(&mut self, id: u32) -> io::Result<ControlValue> {
+        let control = self.inner.control(id)?;
+        let value = match control.value {
+            // v4l's `Value` has no dedicated Menu/Bitmask/Button variant, so a plain integer
+            // needs to be reinterpreted according to the control's actual representation.
+            PlatformControlValue::Integer(v) => self.control_repr(id)?.wrap_integer(v),
+            PlatformControlValue::Boolean(v) => ControlValue::Boolean(v),
+            PlatformControlValue::String(v) => ControlValue::String(v),
+        };
+        Ok(value)
+    }
+
+    fn set_control(&mut self, id: u32, value: ControlValue) -> io::Result<()> {
+        let repr = self.control_repr(id)?;
+        repr.validate(&value)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+        let plat_value = match value {
+            ControlValue::Integer(v) => PlatformControlValue::Integer(v),
+            ControlValue::Boolean(v) => PlatformControlValue::Boolean(v),
+            ControlValue::String(v) => PlatformControlValue::String(v),
+            ControlValue::Menu(index) => PlatformControlValue::Integer(index as i64),
+            ControlValue::Bitmask(bits) => PlatformControlValue::Integer(bits as i64),
+            ControlValue::Button => PlatformControlValue::Integer(1),
+        };
+        self.inner.set_control(PlatformControl {
+            id,
+            value: plat_value,
+        })
+    }
+
+    fn stream_with<'a>(
+        &'a mut self,
+        cfg: StreamConfig,
+    ) -> io::Result<Box<dyn Stream<Item = DynamicImageView> + 'a>> {
+        let stream = PlatformStream::with_config(self, cfg)?;
         Ok(Box::new(stream))
     }
 }
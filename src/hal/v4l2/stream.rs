@@ -0,0 +1,78 @@
+use std::io;
+
+use ffimage::packed::DynamicImageView;
+
+use v4l::buffer::Type as BufferType;
+use v4l::io::mmap::Stream as MmapStream;
+use v4l::io::read::Stream as ReadStream;
+use v4l::io::traits::CaptureStream;
+use v4l::io::userptr::Stream as UserptrStream;
+
+use crate::format::Format;
+use crate::hal::traits::{Device, IoMethod, StreamConfig};
+use crate::hal::v4l2::device::PlatformDevice;
+use crate::traits::Stream;
+
+/// The underlying v4l2 I/O backend a `PlatformStream` pulls buffers from.
+enum Handle<'a> {
+    Mmap(MmapStream<'a>),
+    UserPtr(UserptrStream<'a>),
+    Read(ReadStream<'a>),
+}
+
+impl<'a> Handle<'a> {
+    fn next(&mut self) -> io::Result<&[u8]> {
+        let (buf, _meta) = match self {
+            Handle::Mmap(handle) => handle.next()?,
+            Handle::UserPtr(handle) => handle.next()?,
+            Handle::Read(handle) => handle.next()?,
+        };
+        Ok(buf)
+    }
+}
+
+pub(crate) struct PlatformStream<'a> {
+    handle: Handle<'a>,
+    format: Format,
+    view: Option<DynamicImageView>,
+}
+
+impl<'a> PlatformStream<'a> {
+    pub fn with_config(dev: &'a mut PlatformDevice, cfg: StreamConfig) -> io::Result<Self> {
+        let format = dev.get_format()?;
+        let inner = dev.inner_mut();
+        let handle = match cfg.io_method {
+            IoMethod::Mmap => Handle::Mmap(MmapStream::with_buffers(
+                inner,
+                BufferType::VideoCapture,
+                cfg.buffer_count,
+            )?),
+            IoMethod::UserPtr => Handle::UserPtr(UserptrStream::with_buffers(
+                inner,
+                BufferType::VideoCapture,
+                cfg.buffer_count,
+            )?),
+            // read() has no queue to size; every call blocks for exactly one frame.
+            IoMethod::Read => Handle::Read(ReadStream::new(inner, BufferType::VideoCapture)?),
+        };
+        Ok(PlatformStream {
+            handle,
+            format,
+            view: None,
+        })
+    }
+}
+
+impl<'a> Stream for PlatformStream<'a> {
+    type Item = DynamicImageView;
+
+    fn next(&mut self) -> io::Result<&Self::Item> {
+        let buf = self.handle.next()?;
+        self.view = Some(DynamicImageView::new(
+            buf,
+            self.format.width as usize,
+            self.format.height as usize,
+        ));
+        Ok(self.view.as_ref().unwrap())
+    }
+}
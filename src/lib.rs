@@ -0,0 +1,7 @@
+pub mod control;
+pub mod convert;
+pub mod device;
+pub mod format;
+pub mod traits;
+
+pub(crate) mod hal;
@@ -0,0 +1,12 @@
+use std::io;
+
+/// A stream of items, typically video frames, pulled from a device.
+pub trait Stream {
+    /// The kind of item produced by the stream, e.g. an image view.
+    type Item;
+
+    /// Returns the next item in the stream.
+    ///
+    /// This call blocks until a new item is available or an I/O error occurs.
+    fn next(&mut self) -> io::Result<&Self::Item>;
+}